@@ -1,5 +1,6 @@
 use super::{Rect, Room};
-use rltk::{Algorithm2D, BaseMap, Point, RandomNumberGenerator, Rltk, RGB};
+use rltk::{Algorithm2D, BaseMap, DistanceAlg, Point, Rltk, SmallVec, RGB};
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 use std::cmp::{max, min};
 
@@ -38,6 +39,9 @@ mod tests {
             Map::new,
             Map::new_map_rooms_and_corridors,
             Map::new_map_all_open,
+            Map::new_map_cellular,
+            Map::new_map_bsp_rooms,
+            Map::new_map_bsp_interior,
         ];
 
         for algo in algo_list.iter() {
@@ -63,6 +67,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn borders_check_cellular() -> Result<(), String> {
+        let map = Map::new_map_cellular(0);
+        borders_check(map)?;
+        Ok(())
+    }
+
+    #[test]
+    fn borders_check_bsp_rooms() -> Result<(), String> {
+        let map = Map::new_map_bsp_rooms(0);
+        borders_check(map)?;
+        Ok(())
+    }
+
+    #[test]
+    fn borders_check_bsp_interior() -> Result<(), String> {
+        let map = Map::new_map_bsp_interior(0);
+        borders_check(map)?;
+        Ok(())
+    }
+
+    #[test]
+    fn borders_check_mirrored() -> Result<(), String> {
+        let map = crate::mapgen::MapBuilder::new(80, 50)
+            .with(crate::mapgen::RoomsAndCorridors)
+            .with(crate::mapgen::Mirror::new(crate::mapgen::Symmetry::Both))
+            .build(0);
+        borders_check(map)?;
+        Ok(())
+    }
+
     #[test]
     #[ignore]
     fn borders_check_corner_ul() {
@@ -102,12 +137,14 @@ mod tests {
     }
 }
 
-#[derive(PartialEq, Copy, Clone)]
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub enum TileType {
     Wall,
     Floor,
+    DownStairs,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Map {
     pub tiles: Vec<TileType>,
     pub rooms: Vec<Rect>,
@@ -115,6 +152,7 @@ pub struct Map {
     pub height: i32,
     pub revealed_tiles: Vec<bool>,
     pub visible_tiles: Vec<bool>,
+    pub blocked: Vec<bool>,
     pub depth: i32,
 }
 
@@ -127,11 +165,12 @@ impl Map {
             height: 50,
             revealed_tiles: vec![false; 80 * 50],
             visible_tiles: vec![false; 80 * 50],
+            blocked: vec![false; 80 * 50],
             depth: new_depth,
         }
     }
 
-    fn new_with_dimensions(w: usize, h: usize, new_depth: i32) -> Map {
+    pub(crate) fn new_with_dimensions(w: usize, h: usize, new_depth: i32) -> Map {
         Map {
             tiles: vec![TileType::Wall; w * h],
             rooms: Vec::new(),
@@ -139,15 +178,30 @@ impl Map {
             height: h as i32,
             revealed_tiles: vec![false; w * h],
             visible_tiles: vec![false; w * h],
+            blocked: vec![false; w * h],
             depth: new_depth,
         }
     }
 
+    /// Refreshes `blocked` from the current tiles: a tile blocks movement
+    /// iff it's a `Wall`. Call this after any generation/modification pass
+    /// and before pathfinding against the map.
+    pub fn populate_blocked(&mut self) {
+        for (idx, tile) in self.tiles.iter().enumerate() {
+            self.blocked[idx] = *tile == TileType::Wall;
+        }
+    }
+
     pub fn xy_idx(&self, x: i32, y: i32) -> usize {
         (y as usize * self.width as usize) + x as usize
     }
 
-    fn apply_room_to_map(&mut self, room: &dyn Room) {
+    /// Inverse of `xy_idx`: recovers the `(x, y)` coordinate of a tile index.
+    pub fn idx_xy(&self, idx: usize) -> (i32, i32) {
+        (idx as i32 % self.width, idx as i32 / self.width)
+    }
+
+    pub(crate) fn apply_room_to_map(&mut self, room: &dyn Room) {
         for (x, y) in room.spaces().iter() {
             let idx = self.xy_idx(*x, *y);
             self.tiles[idx] = TileType::Floor;
@@ -156,81 +210,106 @@ impl Map {
 
     /// "Digs out" a horizontal corridor (changes Wall -> Floor)
     /// given x1, x2, y
-    fn apply_horizontal_tunnel(&mut self, x1: i32, x2: i32, y: i32) {
+    pub(crate) fn apply_horizontal_tunnel(&mut self, x1: i32, x2: i32, y: i32) {
         for x in min(x1, x2)..=max(x1, x2) {
             let idx = self.xy_idx(x, y);
             if idx > 0 && idx < self.width as usize * self.height as usize {
-                self.tiles[idx as usize] = TileType::Floor;
+                self.tiles[idx] = TileType::Floor;
             }
         }
     }
 
     /// "Digs out" a vertical corridor (changes Wall -> Floor)
     /// given y1, y2, x
-    fn apply_vertical_tunnel(&mut self, y1: i32, y2: i32, x: i32) {
+    pub(crate) fn apply_vertical_tunnel(&mut self, y1: i32, y2: i32, x: i32) {
         for y in min(y1, y2)..=max(y1, y2) {
             let idx = self.xy_idx(x, y);
             if idx > 0 && idx < self.width as usize * self.height as usize {
-                self.tiles[idx as usize] = TileType::Floor;
+                self.tiles[idx] = TileType::Floor;
             }
         }
     }
 
     pub fn new_map_all_open(new_depth: i32) -> Map {
-
         let mut map = Map::new(new_depth);
 
         let new_room = Rect::new(0, 0, 78, 48);
         map.apply_room_to_map(&new_room);
         map.rooms.push(new_room);
+        map.populate_blocked();
 
         map
     }
 
     /// Makes a new map using the algorithm from http://rogueliketutorials.com/tutorials/tcod/part-3/
     /// This gives a handful of random rooms and corridors joining them together.
+    ///
+    /// This is a thin convenience wrapper around the `MapBuilder` pipeline
+    /// (`MapBuilder::new(80, 50).with(RoomsAndCorridors)`, followed by
+    /// `CullUnreachable` and `PlaceDownStairs`); reach for the builder
+    /// directly when composing a different set of stages.
     pub fn new_map_rooms_and_corridors(new_depth: i32) -> Map {
-        // base map
-        let mut map = Map::new_with_dimensions(80, 50, new_depth);
-
-        const MAX_ROOMS: i32 = 30;
-        const MIN_SIZE: i32 = 6;
-        const MAX_SIZE: i32 = 10;
-
-        let mut rng = RandomNumberGenerator::new();
-
-        for _ in 0..MAX_ROOMS {
-            let w = rng.range(MIN_SIZE, MAX_SIZE);
-            let h = rng.range(MIN_SIZE, MAX_SIZE);
-            let x = rng.roll_dice(1, map.width - w - 1) - 1;
-            let y = rng.roll_dice(1, map.height - h - 1) - 1;
-            let new_room = Rect::new(x, y, w, h);
-            let mut ok = true;
-            for other_room in map.rooms.iter() {
-                if new_room.intersect(other_room) {
-                    ok = false;
-                }
-            }
-            if ok {
-                map.apply_room_to_map(&new_room);
-
-                if !map.rooms.is_empty() {
-                    let (new_x, new_y) = new_room.center();
-                    let (prev_x, prev_y) = map.rooms[map.rooms.len() - 1].center();
-                    if rng.range(0, 2) == 1 {
-                        map.apply_horizontal_tunnel(prev_x, new_x, prev_y);
-                        map.apply_vertical_tunnel(prev_y, new_y, new_x);
-                    } else {
-                        map.apply_vertical_tunnel(prev_y, new_y, prev_x);
-                        map.apply_horizontal_tunnel(prev_x, new_x, new_y);
-                    }
-                }
+        crate::mapgen::MapBuilder::new(80, 50)
+            .with(crate::mapgen::RoomsAndCorridors)
+            .with(crate::mapgen::CullUnreachable::from_first_room())
+            .with(crate::mapgen::PlaceDownStairs)
+            .build(new_depth)
+    }
 
-                map.rooms.push(new_room);
-            }
-        }
+    /// Makes a new map by seeding noise and smoothing it with a cellular
+    /// automaton, producing an organic cavern layout rather than
+    /// rectangular rooms.
+    pub fn new_map_cellular(new_depth: i32) -> Map {
+        crate::mapgen::MapBuilder::new(80, 50)
+            .with(crate::mapgen::CellularAutomata)
+            .with(crate::mapgen::CullUnreachable::from_first_room())
+            .with(crate::mapgen::PlaceDownStairs)
+            .build(new_depth)
+    }
 
-        map
+    /// Makes a new map by recursively splitting the interior with a
+    /// binary space partition, carving an inset room into each leaf and
+    /// joining siblings with corridors.
+    ///
+    /// Thin convenience wrapper around
+    /// `MapBuilder::new(80, 50).with(BspRooms)`, followed by
+    /// `CullUnreachable` and `PlaceDownStairs`.
+    pub fn new_map_bsp_rooms(new_depth: i32) -> Map {
+        crate::mapgen::MapBuilder::new(80, 50)
+            .with(crate::mapgen::BspRooms)
+            .with(crate::mapgen::CullUnreachable::from_first_room())
+            .with(crate::mapgen::PlaceDownStairs)
+            .build(new_depth)
+    }
+
+    /// Makes a new map by recursively splitting the interior with a
+    /// binary space partition and carving the full rectangle of every
+    /// leaf, producing a dense packed-room layout.
+    ///
+    /// Thin convenience wrapper around
+    /// `MapBuilder::new(80, 50).with(BspInterior)`, followed by
+    /// `CullUnreachable` and `PlaceDownStairs`.
+    pub fn new_map_bsp_interior(new_depth: i32) -> Map {
+        crate::mapgen::MapBuilder::new(80, 50)
+            .with(crate::mapgen::BspInterior)
+            .with(crate::mapgen::CullUnreachable::from_first_room())
+            .with(crate::mapgen::PlaceDownStairs)
+            .build(new_depth)
+    }
+
+    /// Builds a fresh map for the next level down, at `depth + 1`.
+    pub fn next_level(&self) -> Map {
+        Map::new_map_rooms_and_corridors(self.depth + 1)
+    }
+
+    /// Serializes this map to a JSON string, for use in game saves.
+    pub fn save_to_string(&self) -> String {
+        serde_json::to_string(self).expect("Map failed to serialize")
+    }
+
+    /// Restores a map previously produced by `save_to_string`.
+    pub fn load_from_string(saved: &str) -> Map {
+        serde_json::from_str(saved).expect("Map failed to deserialize")
     }
 }
 
@@ -242,7 +321,48 @@ impl Algorithm2D for Map {
 
 impl BaseMap for Map {
     fn is_opaque(&self, idx: usize) -> bool {
-        self.tiles[idx as usize] == TileType::Wall
+        self.tiles[idx] == TileType::Wall
+    }
+
+    fn get_available_exits(&self, idx: usize) -> SmallVec<[(usize, f32); 10]> {
+        let mut exits = SmallVec::new();
+        let (x, y) = self.idx_xy(idx);
+
+        // Cardinal neighbors (cost 1.0)
+        if x > 0 && !self.blocked[self.xy_idx(x - 1, y)] {
+            exits.push((self.xy_idx(x - 1, y), 1.0));
+        }
+        if x < self.width - 1 && !self.blocked[self.xy_idx(x + 1, y)] {
+            exits.push((self.xy_idx(x + 1, y), 1.0));
+        }
+        if y > 0 && !self.blocked[self.xy_idx(x, y - 1)] {
+            exits.push((self.xy_idx(x, y - 1), 1.0));
+        }
+        if y < self.height - 1 && !self.blocked[self.xy_idx(x, y + 1)] {
+            exits.push((self.xy_idx(x, y + 1), 1.0));
+        }
+
+        // Diagonal neighbors (cost 1.45)
+        if x > 0 && y > 0 && !self.blocked[self.xy_idx(x - 1, y - 1)] {
+            exits.push((self.xy_idx(x - 1, y - 1), 1.45));
+        }
+        if x < self.width - 1 && y > 0 && !self.blocked[self.xy_idx(x + 1, y - 1)] {
+            exits.push((self.xy_idx(x + 1, y - 1), 1.45));
+        }
+        if x > 0 && y < self.height - 1 && !self.blocked[self.xy_idx(x - 1, y + 1)] {
+            exits.push((self.xy_idx(x - 1, y + 1), 1.45));
+        }
+        if x < self.width - 1 && y < self.height - 1 && !self.blocked[self.xy_idx(x + 1, y + 1)] {
+            exits.push((self.xy_idx(x + 1, y + 1), 1.45));
+        }
+
+        exits
+    }
+
+    fn get_pathing_distance(&self, idx1: usize, idx2: usize) -> f32 {
+        let p1 = self.index_to_point2d(idx1);
+        let p2 = self.index_to_point2d(idx2);
+        DistanceAlg::Pythagoras.distance2d(p1, p2)
     }
 }
 
@@ -266,6 +386,10 @@ pub fn draw_map(ecs: &World, ctx: &mut Rltk) {
                     glyph = rltk::to_cp437('#');
                     fg = RGB::from_f32(0., 1.0, 0.);
                 }
+                TileType::DownStairs => {
+                    glyph = rltk::to_cp437('>');
+                    fg = RGB::from_f32(0., 1.0, 1.0);
+                }
             }
             if !map.visible_tiles[idx] {
                 fg = fg.to_greyscale()
@@ -0,0 +1,208 @@
+use super::super::{Map, Rect, Room};
+use super::MapModifier;
+use rltk::RandomNumberGenerator;
+
+const MIN_LEAF_SIZE: i32 = 8;
+
+/// A rectangular region of the map produced by recursively splitting.
+struct Partition {
+    x1: i32,
+    y1: i32,
+    x2: i32,
+    y2: i32,
+}
+
+impl Partition {
+    fn width(&self) -> i32 {
+        self.x2 - self.x1
+    }
+
+    fn height(&self) -> i32 {
+        self.y2 - self.y1
+    }
+}
+
+/// A binary space partition tree: either an undivided leaf region, or a
+/// split into two sibling subtrees.
+enum BspNode {
+    Leaf(Partition),
+    Split(Box<BspNode>, Box<BspNode>),
+}
+
+/// Recursively splits `bounds` on its longer axis (offset randomized to
+/// the middle 40-60% of that axis) until a subregion's longer side is at
+/// most `MIN_LEAF_SIZE * 2`, at which point it becomes a leaf.
+fn partition(rng: &mut RandomNumberGenerator, bounds: Partition) -> BspNode {
+    let w = bounds.width();
+    let h = bounds.height();
+
+    if w.max(h) < MIN_LEAF_SIZE * 2 + 1 {
+        return BspNode::Leaf(bounds);
+    }
+
+    if w >= h {
+        let split_x = bounds.x1 + rng.range(w * 4 / 10, w * 6 / 10);
+        let first = Partition {
+            x1: bounds.x1,
+            y1: bounds.y1,
+            x2: split_x - 1,
+            y2: bounds.y2,
+        };
+        let second = Partition {
+            x1: split_x + 1,
+            y1: bounds.y1,
+            x2: bounds.x2,
+            y2: bounds.y2,
+        };
+        BspNode::Split(
+            Box::new(partition(rng, first)),
+            Box::new(partition(rng, second)),
+        )
+    } else {
+        let split_y = bounds.y1 + rng.range(h * 4 / 10, h * 6 / 10);
+        let first = Partition {
+            x1: bounds.x1,
+            y1: bounds.y1,
+            x2: bounds.x2,
+            y2: split_y - 1,
+        };
+        let second = Partition {
+            x1: bounds.x1,
+            y1: split_y + 1,
+            x2: bounds.x2,
+            y2: bounds.y2,
+        };
+        BspNode::Split(
+            Box::new(partition(rng, first)),
+            Box::new(partition(rng, second)),
+        )
+    }
+}
+
+fn root_partition(map: &Map) -> Partition {
+    Partition {
+        x1: 1,
+        y1: 1,
+        x2: map.width - 2,
+        y2: map.height - 2,
+    }
+}
+
+/// Carves a slightly-inset room inside each BSP leaf and joins sibling
+/// leaves' room centers with an L-shaped corridor.
+pub struct BspRooms;
+
+impl MapModifier for BspRooms {
+    fn modify_map(&self, rng: &mut RandomNumberGenerator, map: &mut Map) {
+        let tree = partition(rng, root_partition(map));
+        carve_rooms(map, &tree);
+    }
+}
+
+fn carve_rooms(map: &mut Map, node: &BspNode) -> (i32, i32) {
+    match node {
+        BspNode::Leaf(p) => {
+            let room = Rect::new(
+                p.x1 + 1,
+                p.y1 + 1,
+                (p.width() - 2).max(1),
+                (p.height() - 2).max(1),
+            );
+            map.apply_room_to_map(&room);
+            let center = room.center();
+            map.rooms.push(room);
+            center
+        }
+        BspNode::Split(first, second) => {
+            let first_center = carve_rooms(map, first);
+            let second_center = carve_rooms(map, second);
+            map.apply_horizontal_tunnel(first_center.0, second_center.0, first_center.1);
+            map.apply_vertical_tunnel(first_center.1, second_center.1, second_center.0);
+            first_center
+        }
+    }
+}
+
+/// Carves the full rectangle of every BSP leaf, leaving only the
+/// partition gaps as walls between them, and cuts an L-shaped opening
+/// between sibling leaves' centers so every leaf is reachable. Produces a
+/// dense, packed-room layout.
+pub struct BspInterior;
+
+impl MapModifier for BspInterior {
+    fn modify_map(&self, rng: &mut RandomNumberGenerator, map: &mut Map) {
+        let tree = partition(rng, root_partition(map));
+        carve_interior(map, &tree);
+    }
+}
+
+fn carve_interior(map: &mut Map, node: &BspNode) -> (i32, i32) {
+    match node {
+        BspNode::Leaf(p) => {
+            // `Rect::spaces()` insets by 1 on the top/left edge, so shift
+            // x1/y1 out by 1 to carve the leaf's full bounds; otherwise
+            // that inset stacks with the wall column/row `partition`
+            // already reserves between siblings, widening the gap.
+            let room = Rect {
+                x1: p.x1 - 1,
+                y1: p.y1 - 1,
+                x2: p.x2,
+                y2: p.y2,
+            };
+            map.apply_room_to_map(&room);
+            let center = room.center();
+            map.rooms.push(room);
+            center
+        }
+        BspNode::Split(first, second) => {
+            let first_center = carve_interior(map, first);
+            let second_center = carve_interior(map, second);
+            map.apply_horizontal_tunnel(first_center.0, second_center.0, first_center.1);
+            map.apply_vertical_tunnel(first_center.1, second_center.1, second_center.0);
+            first_center
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapgen::cull_unreachable::flood_fill;
+    use crate::mapgen::{CullUnreachable, MapBuilder};
+    use crate::TileType;
+
+    /// A BFS from `map.rooms[0]`'s center must reach every `Floor` tile.
+    fn assert_connected(map: &Map) {
+        let (start_x, start_y) = map.rooms[0].center();
+        let reachable = flood_fill(map, start_x, start_y);
+        for (idx, tile) in map.tiles.iter().enumerate() {
+            if *tile == TileType::Floor {
+                assert!(
+                    reachable[idx],
+                    "floor tile at {:?} unreachable after culling",
+                    map.idx_xy(idx)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bsp_rooms_is_connected_after_culling() {
+        let map = MapBuilder::new(80, 50)
+            .with(BspRooms)
+            .with(CullUnreachable::from_first_room())
+            .build(0);
+        assert_connected(&map);
+    }
+
+    /// Unlike `bsp_rooms_is_connected_after_culling`, this deliberately
+    /// does *not* chain `CullUnreachable` — flooding from the same start
+    /// tile that `CullUnreachable` would use can't distinguish "the
+    /// generator connects its leaves" from "culling threw away
+    /// everything else", so this checks `BspInterior`'s raw output.
+    #[test]
+    fn bsp_interior_leaves_are_connected() {
+        let map = MapBuilder::new(80, 50).with(BspInterior).build(0);
+        assert_connected(&map);
+    }
+}
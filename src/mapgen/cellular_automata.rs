@@ -0,0 +1,77 @@
+use super::super::{Map, TileType};
+use super::MapModifier;
+use rltk::RandomNumberGenerator;
+
+const SMOOTHING_PASSES: i32 = 12;
+const WALL_SEED_CHANCE: i32 = 45;
+
+/// Carves an organic cavern by seeding noise and smoothing it with a
+/// cellular automaton, instead of placing discrete rectangular rooms.
+///
+/// Interior tiles start `Wall` with ~45% probability. Each smoothing pass
+/// then turns a tile `Wall` if 5+ of its 8 Moore neighbours (treating
+/// out-of-bounds as `Wall`) are walls, `Floor` otherwise. Leaves
+/// `map.rooms` empty and may leave disconnected pockets; pair with
+/// `CullUnreachable`.
+pub struct CellularAutomata;
+
+impl MapModifier for CellularAutomata {
+    fn modify_map(&self, rng: &mut RandomNumberGenerator, map: &mut Map) {
+        seed(rng, map);
+        for _ in 0..SMOOTHING_PASSES {
+            smooth(map);
+        }
+    }
+}
+
+fn seed(rng: &mut RandomNumberGenerator, map: &mut Map) {
+    for y in 1..map.height - 1 {
+        for x in 1..map.width - 1 {
+            let idx = map.xy_idx(x, y);
+            let roll = rng.roll_dice(1, 100);
+            map.tiles[idx] = if roll <= WALL_SEED_CHANCE {
+                TileType::Wall
+            } else {
+                TileType::Floor
+            };
+        }
+    }
+}
+
+fn smooth(map: &mut Map) {
+    let mut new_tiles = map.tiles.clone();
+    for y in 1..map.height - 1 {
+        for x in 1..map.width - 1 {
+            let neighbour_walls = count_wall_neighbours(map, x, y);
+            let idx = map.xy_idx(x, y);
+            new_tiles[idx] = if neighbour_walls >= 5 {
+                TileType::Wall
+            } else {
+                TileType::Floor
+            };
+        }
+    }
+    map.tiles = new_tiles;
+}
+
+fn count_wall_neighbours(map: &Map, x: i32, y: i32) -> i32 {
+    let mut walls = 0;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+            let nx = x + dx;
+            let ny = y + dy;
+            let is_wall = if nx < 0 || nx >= map.width || ny < 0 || ny >= map.height {
+                true
+            } else {
+                map.tiles[map.xy_idx(nx, ny)] == TileType::Wall
+            };
+            if is_wall {
+                walls += 1;
+            }
+        }
+    }
+    walls
+}
@@ -0,0 +1,150 @@
+use super::super::{Map, Room, TileType};
+use super::MapModifier;
+use rltk::RandomNumberGenerator;
+use std::collections::VecDeque;
+
+/// Where a `CullUnreachable` pass should start its flood fill from.
+enum Start {
+    Point(i32, i32),
+    FirstRoomCenter,
+}
+
+/// Flood-fills `Floor` tiles (4-connectivity) from a start tile and turns
+/// every tile that was never reached back into `Wall`. Run before placing
+/// stairs or the player.
+pub struct CullUnreachable {
+    start: Start,
+}
+
+impl CullUnreachable {
+    /// Floods from an explicit `(x, y)` tile.
+    pub fn from_point(x: i32, y: i32) -> CullUnreachable {
+        CullUnreachable {
+            start: Start::Point(x, y),
+        }
+    }
+
+    /// Floods from the center of the first carved room.
+    pub fn from_first_room() -> CullUnreachable {
+        CullUnreachable {
+            start: Start::FirstRoomCenter,
+        }
+    }
+}
+
+impl MapModifier for CullUnreachable {
+    fn modify_map(&self, _rng: &mut RandomNumberGenerator, map: &mut Map) {
+        let (start_x, start_y) = match self.start {
+            Start::Point(x, y) => (x, y),
+            Start::FirstRoomCenter => map
+                .rooms
+                .first()
+                .map(|room| room.center())
+                .unwrap_or_else(|| first_floor_tile(map)),
+        };
+
+        let reachable = flood_fill(map, start_x, start_y);
+        for (idx, tile) in map.tiles.iter_mut().enumerate() {
+            if *tile == TileType::Floor && !reachable[idx] {
+                *tile = TileType::Wall;
+            }
+        }
+    }
+}
+
+/// Finds any `Floor` tile, for generators that never populate `map.rooms`.
+/// Falls back to `(1, 1)` if the map somehow has no `Floor` tiles at all.
+pub(crate) fn first_floor_tile(map: &Map) -> (i32, i32) {
+    map.tiles
+        .iter()
+        .position(|tile| *tile == TileType::Floor)
+        .map(|idx| map.idx_xy(idx))
+        .unwrap_or((1, 1))
+}
+
+/// BFS flood fill over `Floor` tiles, 4-connected, from `(start_x, start_y)`.
+/// Returns a `Vec<bool>` the same length as `map.tiles`, true at every
+/// reached index.
+pub(crate) fn flood_fill(map: &Map, start_x: i32, start_y: i32) -> Vec<bool> {
+    let mut reached = vec![false; map.tiles.len()];
+    let start_idx = map.xy_idx(start_x, start_y);
+    if map.tiles[start_idx] != TileType::Floor {
+        return reached;
+    }
+
+    let mut frontier = VecDeque::new();
+    reached[start_idx] = true;
+    frontier.push_back(start_idx);
+
+    while let Some(idx) = frontier.pop_front() {
+        let (x, y) = map.idx_xy(idx);
+        for (nx, ny) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)].iter() {
+            if *nx < 0 || *nx >= map.width || *ny < 0 || *ny >= map.height {
+                continue;
+            }
+            let nidx = map.xy_idx(*nx, *ny);
+            if !reached[nidx] && map.tiles[nidx] == TileType::Floor {
+                reached[nidx] = true;
+                frontier.push_back(nidx);
+            }
+        }
+    }
+
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapgen::{CellularAutomata, MapBuilder, RoomsAndCorridors};
+
+    /// After culling, a BFS from the start must reach every remaining
+    /// `Floor` tile, i.e. nothing was left stranded behind the cull.
+    #[test]
+    fn cull_unreachable_leaves_only_reachable_floors() {
+        let map = MapBuilder::new(80, 50)
+            .with(RoomsAndCorridors)
+            .with(CullUnreachable::from_first_room())
+            .build(0);
+
+        let (start_x, start_y) = map.rooms[0].center();
+        let reachable = flood_fill(&map, start_x, start_y);
+
+        for (idx, tile) in map.tiles.iter().enumerate() {
+            if *tile == TileType::Floor {
+                assert!(
+                    reachable[idx],
+                    "floor tile at {:?} unreachable after culling",
+                    map.idx_xy(idx)
+                );
+            }
+        }
+    }
+
+    /// `CellularAutomata` never populates `map.rooms`, so
+    /// `from_first_room` must fall back to scanning for a real `Floor`
+    /// tile instead of assuming `(1, 1)` is one.
+    #[test]
+    fn cull_unreachable_after_cellular_automata_leaves_only_reachable_floors() {
+        let map = MapBuilder::new(80, 50)
+            .with(CellularAutomata)
+            .with(CullUnreachable::from_first_room())
+            .build(0);
+
+        let (start_x, start_y) = first_floor_tile(&map);
+        let reachable = flood_fill(&map, start_x, start_y);
+
+        let mut saw_floor = false;
+        for (idx, tile) in map.tiles.iter().enumerate() {
+            if *tile == TileType::Floor {
+                saw_floor = true;
+                assert!(
+                    reachable[idx],
+                    "floor tile at {:?} unreachable after culling",
+                    map.idx_xy(idx)
+                );
+            }
+        }
+        assert!(saw_floor, "map has no floor tiles at all");
+    }
+}
@@ -0,0 +1,66 @@
+use super::Map;
+use rltk::RandomNumberGenerator;
+
+mod bsp;
+mod cellular_automata;
+mod cull_unreachable;
+mod rooms_and_corridors;
+mod stairs;
+mod symmetry;
+
+pub use bsp::{BspInterior, BspRooms};
+pub use cellular_automata::CellularAutomata;
+pub use cull_unreachable::CullUnreachable;
+pub use rooms_and_corridors::RoomsAndCorridors;
+pub use stairs::PlaceDownStairs;
+pub use symmetry::{Mirror, Symmetry};
+
+/// A single stage in a map-generation pipeline.
+///
+/// A modifier is handed the in-progress `Map` (and a shared RNG) and
+/// mutates it in place: carving rooms, joining corridors, culling
+/// unreachable pockets, decorating, and so on. Keeping each stage as its
+/// own `MapModifier` lets them be reused and recombined instead of living
+/// inside one monolithic generator function.
+pub trait MapModifier {
+    fn modify_map(&self, rng: &mut RandomNumberGenerator, map: &mut Map);
+}
+
+/// Builds a `Map` by running an ordered chain of `MapModifier`s over an
+/// initially empty map.
+///
+/// ```ignore
+/// let map = MapBuilder::new(80, 50)
+///     .with(RoomsAndCorridors)
+///     .build(1);
+/// ```
+pub struct MapBuilder {
+    map: Map,
+    modifiers: Vec<Box<dyn MapModifier>>,
+}
+
+impl MapBuilder {
+    pub fn new(width: i32, height: i32) -> MapBuilder {
+        MapBuilder {
+            map: Map::new_with_dimensions(width as usize, height as usize, 0),
+            modifiers: Vec::new(),
+        }
+    }
+
+    /// Queues `modifier` to run, in the order added, when `build` is called.
+    pub fn with(mut self, modifier: impl MapModifier + 'static) -> MapBuilder {
+        self.modifiers.push(Box::new(modifier));
+        self
+    }
+
+    /// Runs every queued modifier in sequence and returns the finished map.
+    pub fn build(mut self, depth: i32) -> Map {
+        self.map.depth = depth;
+        let mut rng = RandomNumberGenerator::new();
+        for modifier in self.modifiers.iter() {
+            modifier.modify_map(&mut rng, &mut self.map);
+        }
+        self.map.populate_blocked();
+        self.map
+    }
+}
@@ -0,0 +1,49 @@
+use super::super::{Map, Rect, Room};
+use super::MapModifier;
+use rltk::RandomNumberGenerator;
+
+/// Places up to 30 non-overlapping rectangular rooms and joins each new
+/// room to the previous one with an L-shaped corridor.
+///
+/// Ported from the original `Map::new_map_rooms_and_corridors` monolith;
+/// produces identical output for the same RNG stream.
+pub struct RoomsAndCorridors;
+
+impl MapModifier for RoomsAndCorridors {
+    fn modify_map(&self, rng: &mut RandomNumberGenerator, map: &mut Map) {
+        const MAX_ROOMS: i32 = 30;
+        const MIN_SIZE: i32 = 6;
+        const MAX_SIZE: i32 = 10;
+
+        for _ in 0..MAX_ROOMS {
+            let w = rng.range(MIN_SIZE, MAX_SIZE);
+            let h = rng.range(MIN_SIZE, MAX_SIZE);
+            let x = rng.roll_dice(1, map.width - w - 1) - 1;
+            let y = rng.roll_dice(1, map.height - h - 1) - 1;
+            let new_room = Rect::new(x, y, w, h);
+            let mut ok = true;
+            for other_room in map.rooms.iter() {
+                if new_room.intersect(other_room) {
+                    ok = false;
+                }
+            }
+            if ok {
+                map.apply_room_to_map(&new_room);
+
+                if !map.rooms.is_empty() {
+                    let (new_x, new_y) = new_room.center();
+                    let (prev_x, prev_y) = map.rooms[map.rooms.len() - 1].center();
+                    if rng.range(0, 2) == 1 {
+                        map.apply_horizontal_tunnel(prev_x, new_x, prev_y);
+                        map.apply_vertical_tunnel(prev_y, new_y, new_x);
+                    } else {
+                        map.apply_vertical_tunnel(prev_y, new_y, prev_x);
+                        map.apply_horizontal_tunnel(prev_x, new_x, new_y);
+                    }
+                }
+
+                map.rooms.push(new_room);
+            }
+        }
+    }
+}
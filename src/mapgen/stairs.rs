@@ -0,0 +1,92 @@
+use super::super::{Map, Room, TileType};
+use super::cull_unreachable::first_floor_tile;
+use super::MapModifier;
+use rltk::RandomNumberGenerator;
+use std::collections::VecDeque;
+
+/// Places a `DownStairs` tile at the floor farthest (by walking distance)
+/// from the center of the first room. Run after `CullUnreachable` so the
+/// stairs are guaranteed reachable.
+pub struct PlaceDownStairs;
+
+impl MapModifier for PlaceDownStairs {
+    fn modify_map(&self, _rng: &mut RandomNumberGenerator, map: &mut Map) {
+        let (start_x, start_y) = map
+            .rooms
+            .first()
+            .map(|room| room.center())
+            .unwrap_or_else(|| first_floor_tile(map));
+
+        let (stairs_x, stairs_y) = farthest_floor_from(map, start_x, start_y);
+        let idx = map.xy_idx(stairs_x, stairs_y);
+        map.tiles[idx] = TileType::DownStairs;
+    }
+}
+
+/// Finds the `Floor` tile with the greatest walking distance (BFS,
+/// 4-connected) from `(start_x, start_y)`. Falls back to the start tile
+/// itself if it isn't a `Floor`.
+fn farthest_floor_from(map: &Map, start_x: i32, start_y: i32) -> (i32, i32) {
+    let start_idx = map.xy_idx(start_x, start_y);
+    if map.tiles[start_idx] != TileType::Floor {
+        return (start_x, start_y);
+    }
+
+    let mut dist = vec![-1i32; map.tiles.len()];
+    let mut frontier = VecDeque::new();
+    dist[start_idx] = 0;
+    frontier.push_back(start_idx);
+    let mut farthest_idx = start_idx;
+
+    while let Some(idx) = frontier.pop_front() {
+        let (x, y) = map.idx_xy(idx);
+        for (nx, ny) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)].iter() {
+            if *nx < 0 || *nx >= map.width || *ny < 0 || *ny >= map.height {
+                continue;
+            }
+            let nidx = map.xy_idx(*nx, *ny);
+            if dist[nidx] == -1 && map.tiles[nidx] == TileType::Floor {
+                dist[nidx] = dist[idx] + 1;
+                if dist[nidx] > dist[farthest_idx] {
+                    farthest_idx = nidx;
+                }
+                frontier.push_back(nidx);
+            }
+        }
+    }
+
+    map.idx_xy(farthest_idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapgen::cull_unreachable::flood_fill;
+    use crate::mapgen::{CellularAutomata, CullUnreachable, MapBuilder};
+
+    /// `CellularAutomata` never populates `map.rooms`, so `PlaceDownStairs`
+    /// must resolve its start the same way `CullUnreachable` does, or the
+    /// stairs end up placed off in solid rock instead of on the reachable
+    /// floor farthest from the start.
+    #[test]
+    fn down_stairs_are_reachable_after_cellular_automata() {
+        let mut map = MapBuilder::new(80, 50)
+            .with(CellularAutomata)
+            .with(CullUnreachable::from_first_room())
+            .with(PlaceDownStairs)
+            .build(0);
+
+        let stairs_idx = map
+            .tiles
+            .iter()
+            .position(|tile| *tile == TileType::DownStairs)
+            .expect("no DownStairs tile placed");
+
+        // `flood_fill` only walks `Floor` tiles, so let it step onto the
+        // stairs tile.
+        map.tiles[stairs_idx] = TileType::Floor;
+        let (start_x, start_y) = first_floor_tile(&map);
+        let reachable = flood_fill(&map, start_x, start_y);
+        assert!(reachable[stairs_idx], "DownStairs tile is unreachable");
+    }
+}
@@ -0,0 +1,87 @@
+use super::super::{Map, TileType};
+use super::MapModifier;
+use rltk::RandomNumberGenerator;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mirroring `Both` ways should make every quadrant-reflected tile of
+    /// a carved floor a floor too.
+    #[test]
+    fn mirror_both_is_symmetric() {
+        let mut map = Map::new_with_dimensions(20, 20, 0);
+        let idx = map.xy_idx(3, 4);
+        map.tiles[idx] = TileType::Floor;
+
+        Mirror::new(Symmetry::Both).modify_map(&mut RandomNumberGenerator::new(), &mut map);
+
+        for (x, y) in [(3, 4), (16, 4), (3, 15), (16, 15)].iter() {
+            let idx = map.xy_idx(*x, *y);
+            assert!(map.tiles[idx] == TileType::Floor);
+        }
+    }
+}
+
+/// Which axis (if any) a `Mirror` modifier should reflect carved floors
+/// across, to build vault/arena-style symmetric layouts.
+#[derive(PartialEq, Copy, Clone)]
+pub enum Symmetry {
+    None,
+    Horizontal,
+    Vertical,
+    Both,
+}
+
+/// Mirrors the floors carved by earlier stages across the center of the
+/// map. `Horizontal` reflects `(x, y)` to `(width - 1 - x, y)`, `Vertical`
+/// to `(x, height - 1 - y)`, `Both` into all four quadrants. Only ever
+/// turns `Wall` into `Floor`.
+pub struct Mirror {
+    symmetry: Symmetry,
+}
+
+impl Mirror {
+    pub fn new(symmetry: Symmetry) -> Mirror {
+        Mirror { symmetry }
+    }
+}
+
+impl MapModifier for Mirror {
+    fn modify_map(&self, _rng: &mut RandomNumberGenerator, map: &mut Map) {
+        if self.symmetry == Symmetry::None {
+            return;
+        }
+
+        let floors: Vec<(i32, i32)> = map
+            .tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| **tile == TileType::Floor)
+            .map(|(idx, _)| map.idx_xy(idx))
+            .collect();
+
+        for (x, y) in floors {
+            if self.symmetry == Symmetry::Horizontal || self.symmetry == Symmetry::Both {
+                carve(map, map.width - 1 - x, y);
+            }
+            if self.symmetry == Symmetry::Vertical || self.symmetry == Symmetry::Both {
+                carve(map, x, map.height - 1 - y);
+            }
+            if self.symmetry == Symmetry::Both {
+                carve(map, map.width - 1 - x, map.height - 1 - y);
+            }
+        }
+    }
+}
+
+/// Carves `(x, y)` to `Floor` unless it falls on the outer border.
+fn carve(map: &mut Map, x: i32, y: i32) {
+    if x <= 0 || x >= map.width - 1 || y <= 0 || y >= map.height - 1 {
+        return;
+    }
+    let idx = map.xy_idx(x, y);
+    if map.tiles[idx] == TileType::Wall {
+        map.tiles[idx] = TileType::Floor;
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 /// Trait for rooms
 pub trait Room {
     /// Returns true if this overlaps with other
@@ -5,9 +7,11 @@ pub trait Room {
         let _own_spaces = self.spaces();
         let _other_spaces = other.spaces();
 
-        _own_spaces.iter()
+        _own_spaces
+            .iter()
             .filter(|x| _other_spaces.contains(x))
-            .count() == 0
+            .count()
+            > 0
     }
 
     /// Returns a coordinate pair (x, y) of the center of the room
@@ -17,6 +21,7 @@ pub trait Room {
     /// occupies
     fn spaces(&self) -> Vec<(i32, i32)>;
 }
+#[derive(Serialize, Deserialize)]
 pub struct Rect {
     pub x1: i32,
     pub x2: i32,